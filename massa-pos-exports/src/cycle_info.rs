@@ -21,10 +21,272 @@ use nom::{
 use num::rational::Ratio;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, VecDeque};
+use std::io::{Read, Write};
 use std::ops::Bound::Included;
 
 const CYCLE_INFO_HASH_INITIAL_BYTES: &[u8; 32] = &[0; HASH_SIZE_BYTES];
 
+/// Exact serialized size, in bytes, of `addr` as written by `address_ser`.
+/// Derived by actually serializing into `scratch` (cleared first) rather than
+/// assuming a fixed width, so a future change to the address version varint
+/// (currently always one byte) can't silently under-count and make
+/// `serialize_into` panic on a buffer sized from a stale estimate.
+fn address_serialized_size(
+    address_ser: &AddressSerializer,
+    addr: &Address,
+    scratch: &mut Vec<u8>,
+) -> u64 {
+    scratch.clear();
+    address_ser
+        .serialize(addr, scratch)
+        .expect("address serialization cannot fail");
+    scratch.len() as u64
+}
+
+/// Number of bytes a `u64` takes once encoded as a LEB128-style varint
+pub fn u64_varint_len(v: u64) -> usize {
+    if v == 0 {
+        1
+    } else {
+        1 + ((63 - v.leading_zeros()) / 7) as usize
+    }
+}
+
+/// Serialized size, in bytes, of a `BitVec<u8>` as written by `BitVecSerializer`:
+/// a bit-count `u64` varint followed by the packed bits, rounded up to a byte
+fn bitvec_serialized_size(seed: &BitVec<u8>) -> u64 {
+    u64_varint_len(seed.len() as u64) as u64 + ((seed.len() as u64) + 7) / 8
+}
+
+/// Writes `bytes` into the front of `buf`, advancing the cursor. Panics if
+/// `buf` is not large enough to hold them.
+fn write_into_buf(buf: &mut &mut [u8], bytes: &[u8]) {
+    assert!(
+        buf.len() >= bytes.len(),
+        "serialize_into: destination buffer too small ({} < {})",
+        buf.len(),
+        bytes.len()
+    );
+    let dest = std::mem::take(buf);
+    let (head, tail) = dest.split_at_mut(bytes.len());
+    head.copy_from_slice(bytes);
+    *buf = tail;
+}
+
+/// Serializes `value` with `ser` into `scratch` (cleared first) then copies
+/// the result into `buf`, advancing its cursor. Used to implement
+/// `serialize_into` on top of the existing `Vec`-based `Serializer` impls
+/// without growing the destination buffer.
+fn write_field_into<T>(
+    ser: &impl Serializer<T>,
+    value: &T,
+    scratch: &mut Vec<u8>,
+    buf: &mut &mut [u8],
+) {
+    scratch.clear();
+    ser.serialize(value, scratch)
+        .expect("serialization of this type cannot fail");
+    write_into_buf(buf, scratch);
+}
+
+/// Serializes `value` with `ser` into `scratch` (cleared first) then streams
+/// it into `w`. Used to implement `serialize_to_writer` on top of the
+/// existing `Vec`-based `Serializer` impls, one item at a time, so a whole
+/// collection never has to be materialized in memory.
+fn write_field_to_writer<T, W: Write>(
+    ser: &impl Serializer<T>,
+    value: &T,
+    scratch: &mut Vec<u8>,
+    w: &mut W,
+) -> std::io::Result<()> {
+    scratch.clear();
+    ser.serialize(value, scratch)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?;
+    w.write_all(scratch)
+}
+
+/// Reads one `u64` varint from `r` by pulling bytes until the continuation
+/// bit (the high bit) is clear, then decoding the accumulated bytes
+fn read_u64_varint_from_reader<R: Read>(
+    deser: &U64VarIntDeserializer,
+    r: &mut R,
+) -> std::io::Result<u64> {
+    let mut buf = Vec::with_capacity(10);
+    loop {
+        let mut byte = [0u8; 1];
+        r.read_exact(&mut byte)?;
+        buf.push(byte[0]);
+        if byte[0] & 0x80 == 0 {
+            break;
+        }
+    }
+    let (_, value) = deser
+        .deserialize::<nom::error::Error<&[u8]>>(&buf)
+        .map_err(|e| {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{e:?}"))
+        })?;
+    Ok(value)
+}
+
+/// Reads one `T` from `r` by growing `residual` and retrying `deser` until it
+/// succeeds, so the reader doesn't need to know `T`'s encoded length upfront.
+/// `residual` must be reused across successive calls for the same stream: a
+/// successful parse only drains the bytes it actually consumed, leaving any
+/// over-read bytes (the start of the *next* item) in `residual` instead of
+/// discarding them. Memory use stays bounded by a single `T` plus whatever
+/// of the next item was read ahead, not the whole stream.
+fn deserialize_one_from_reader<T>(
+    deser: &impl Deserializer<T>,
+    r: &mut impl Read,
+    residual: &mut Vec<u8>,
+) -> std::io::Result<T> {
+    let mut chunk = [0u8; 256];
+    loop {
+        if let Ok((rest, value)) = deser.deserialize::<nom::error::Error<&[u8]>>(residual.as_slice())
+        {
+            let consumed = residual.len() - rest.len();
+            residual.drain(..consumed);
+            return Ok(value);
+        }
+        let n = r.read(&mut chunk)?;
+        if n == 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "unexpected end of stream while deserializing",
+            ));
+        }
+        residual.extend_from_slice(&chunk[..n]);
+    }
+}
+
+/// One step of a [`MerkleProof`]: the sibling hash met while climbing from a
+/// leaf to the root, together with which side of the parent it sits on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleProofStep {
+    /// Hash of the sibling node at this level
+    pub sibling: Hash,
+    /// Whether the sibling is the left child of the parent (`true`) or the right one (`false`)
+    pub sibling_is_left: bool,
+}
+
+/// An SPV-style Merkle inclusion proof: the ordered list of sibling hashes
+/// met while climbing from a leaf up to the tree root
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleProof {
+    /// Proof steps, ordered from the leaf level up to (but excluding) the root
+    pub steps: Vec<MerkleProofStep>,
+}
+
+/// Builds the levels of a binary Merkle tree bottom-up from a list of leaves,
+/// duplicating the last node of a level when its length is odd.
+///
+/// Returns one `Vec<Hash>` per level, starting with the leaves and ending
+/// with a single-element `Vec` containing the root. If `leaves` is empty,
+/// the returned root level contains `CYCLE_INFO_HASH_INITIAL_BYTES`.
+fn build_merkle_levels(leaves: Vec<Hash>) -> Vec<Vec<Hash>> {
+    if leaves.is_empty() {
+        return vec![vec![Hash::from_bytes(CYCLE_INFO_HASH_INITIAL_BYTES)]];
+    }
+    let mut levels = vec![leaves];
+    while levels.last().unwrap().len() > 1 {
+        let current = levels.last().unwrap();
+        let mut next = Vec::with_capacity((current.len() + 1) / 2);
+        for pair in current.chunks(2) {
+            let left = pair[0];
+            let right = *pair.get(1).unwrap_or(&pair[0]);
+            let mut buffer = Vec::with_capacity(2 * HASH_SIZE_BYTES);
+            buffer.extend(left.to_bytes());
+            buffer.extend(right.to_bytes());
+            next.push(Hash::compute_from(&buffer));
+        }
+        levels.push(next);
+    }
+    levels
+}
+
+/// Builds an inclusion proof for the leaf at `leaf_index` from the tree `levels`
+/// produced by [`build_merkle_levels`].
+fn build_merkle_proof(levels: &[Vec<Hash>], mut leaf_index: usize) -> MerkleProof {
+    let mut steps = Vec::with_capacity(levels.len().saturating_sub(1));
+    for level in &levels[..levels.len() - 1] {
+        let sibling_index = leaf_index ^ 1;
+        let sibling = *level.get(sibling_index).unwrap_or(&level[leaf_index]);
+        steps.push(MerkleProofStep {
+            sibling,
+            sibling_is_left: leaf_index % 2 == 1,
+        });
+        leaf_index /= 2;
+    }
+    MerkleProof { steps }
+}
+
+/// Recomputes a Merkle root from a leaf hash and its inclusion proof
+fn compute_merkle_root_from_proof(leaf: Hash, proof: &MerkleProof) -> Hash {
+    let mut current = leaf;
+    for step in &proof.steps {
+        let mut buffer = Vec::with_capacity(2 * HASH_SIZE_BYTES);
+        if step.sibling_is_left {
+            buffer.extend(step.sibling.to_bytes());
+            buffer.extend(current.to_bytes());
+        } else {
+            buffer.extend(current.to_bytes());
+            buffer.extend(step.sibling.to_bytes());
+        }
+        current = Hash::compute_from(&buffer);
+    }
+    current
+}
+
+/// Builds a Merkle root over `roll_counts`, with leaves in sorted-address
+/// order. This is an SPV-friendly alternative to `roll_counts_hash` that
+/// supports inclusion proofs via [`CycleInfo::roll_inclusion_proof`] and
+/// [`verify_roll_proof`].
+pub fn build_roll_merkle_root(roll_counts: &BTreeMap<Address, u64>) -> Hash {
+    let hash_computer = CycleInfoHashComputer::new();
+    let leaves = roll_counts
+        .iter()
+        .map(|(addr, &count)| hash_computer.compute_roll_entry_hash(addr, count))
+        .collect();
+    *build_merkle_levels(leaves).last().unwrap().first().unwrap()
+}
+
+/// Builds a Merkle root over `production_stats`, with leaves in sorted-address
+/// order. This is an SPV-friendly alternative to `production_stats_hash` that
+/// supports inclusion proofs via [`CycleInfo::production_stat_inclusion_proof`]
+/// and [`verify_production_stat_proof`].
+pub fn build_production_stats_merkle_root(
+    production_stats: &PreHashMap<Address, ProductionStats>,
+) -> Hash {
+    let hash_computer = CycleInfoHashComputer::new();
+    let sorted: BTreeMap<&Address, &ProductionStats> = production_stats.iter().collect();
+    let leaves = sorted
+        .into_iter()
+        .map(|(addr, prod_stats)| hash_computer.compute_prod_stats_entry_hash(addr, prod_stats))
+        .collect();
+    *build_merkle_levels(leaves).last().unwrap().first().unwrap()
+}
+
+/// Verifies that `addr` holds `count` rolls under the given Merkle `root`,
+/// using the sibling hashes carried by `proof`
+pub fn verify_roll_proof(root: &Hash, addr: &Address, count: u64, proof: &MerkleProof) -> bool {
+    let hash_computer = CycleInfoHashComputer::new();
+    let leaf = hash_computer.compute_roll_entry_hash(addr, count);
+    compute_merkle_root_from_proof(leaf, proof) == *root
+}
+
+/// Verifies that `addr` has the given `prod_stats` under the Merkle `root`,
+/// using the sibling hashes carried by `proof`
+pub fn verify_production_stat_proof(
+    root: &Hash,
+    addr: &Address,
+    prod_stats: &ProductionStats,
+    proof: &MerkleProof,
+) -> bool {
+    let hash_computer = CycleInfoHashComputer::new();
+    let leaf = hash_computer.compute_prod_stats_entry_hash(addr, prod_stats);
+    compute_merkle_root_from_proof(leaf, proof) == *root
+}
+
 struct CycleInfoHashComputer {
     u64_ser: U64VarIntSerializer,
     address_ser: AddressSerializer,
@@ -152,6 +414,174 @@ impl CycleInfo {
             final_state_hash_snapshot: None,
         }
     }
+
+    /// Builds an SPV-style Merkle inclusion proof that `addr` holds its
+    /// current roll count, to be checked with [`verify_roll_proof`] against
+    /// the root returned by [`build_roll_merkle_root`]. Returns `None` if
+    /// `addr` has no entry in `roll_counts`.
+    pub fn roll_inclusion_proof(&self, addr: &Address) -> Option<MerkleProof> {
+        let hash_computer = CycleInfoHashComputer::new();
+        let leaf_index = self.roll_counts.keys().position(|a| a == addr)?;
+        let leaves = self
+            .roll_counts
+            .iter()
+            .map(|(a, &count)| hash_computer.compute_roll_entry_hash(a, count))
+            .collect();
+        let levels = build_merkle_levels(leaves);
+        Some(build_merkle_proof(&levels, leaf_index))
+    }
+
+    /// Builds an SPV-style Merkle inclusion proof that `addr` holds its
+    /// current production stats, to be checked with
+    /// [`verify_production_stat_proof`] against the root returned by
+    /// [`build_production_stats_merkle_root`]. Returns `None` if `addr` has
+    /// no entry in `production_stats`.
+    pub fn production_stat_inclusion_proof(&self, addr: &Address) -> Option<MerkleProof> {
+        let hash_computer = CycleInfoHashComputer::new();
+        let sorted: BTreeMap<&Address, &ProductionStats> = self.production_stats.iter().collect();
+        let leaf_index = sorted.keys().position(|&a| a == addr)?;
+        let leaves = sorted
+            .iter()
+            .map(|(a, prod_stats)| hash_computer.compute_prod_stats_entry_hash(a, prod_stats))
+            .collect();
+        let levels = build_merkle_levels(leaves);
+        Some(build_merkle_proof(&levels, leaf_index))
+    }
+
+    /// Sets the roll count of `addr`, updating `roll_counts_hash` and
+    /// `cycle_global_hash` incrementally in O(1) rather than recomputing
+    /// over the whole `roll_counts` map: the old entry's hash is XORed out
+    /// of the accumulator (if any) and the new entry's hash is XORed in.
+    pub fn set_roll_count(&mut self, addr: Address, count: u64) {
+        let hash_computer = CycleInfoHashComputer::new();
+        if let Some(&old_count) = self.roll_counts.get(&addr) {
+            self.roll_counts_hash ^= hash_computer.compute_roll_entry_hash(&addr, old_count);
+        }
+        self.roll_counts_hash ^= hash_computer.compute_roll_entry_hash(&addr, count);
+        self.roll_counts.insert(addr, count);
+        self.refresh_cycle_global_hash(&hash_computer);
+    }
+
+    /// Merges `stats` into `addr`'s production statistics, updating
+    /// `production_stats_hash` and `cycle_global_hash` incrementally in O(1)
+    /// rather than recomputing over the whole `production_stats` map.
+    pub fn apply_production_stat(&mut self, addr: Address, stats: &ProductionStats) {
+        let hash_computer = CycleInfoHashComputer::new();
+        let mut new_stats = *stats;
+        if let Some(existing) = self.production_stats.get(&addr) {
+            self.production_stats_hash ^=
+                hash_computer.compute_prod_stats_entry_hash(&addr, existing);
+            new_stats = *existing;
+            new_stats.extend(stats);
+        }
+        self.production_stats_hash ^=
+            hash_computer.compute_prod_stats_entry_hash(&addr, &new_stats);
+        self.production_stats.insert(addr, new_stats);
+        self.refresh_cycle_global_hash(&hash_computer);
+    }
+
+    /// Recomputes `cycle_global_hash` from the current `roll_counts_hash` and
+    /// `production_stats_hash` accumulators, without touching them
+    fn refresh_cycle_global_hash(&mut self, hash_computer: &CycleInfoHashComputer) {
+        let mut hash_concat = Vec::new();
+        hash_concat.extend(hash_computer.compute_cycle_hash(self.cycle).to_bytes());
+        hash_concat.extend(hash_computer.compute_complete_hash(self.complete).to_bytes());
+        hash_concat.extend(hash_computer.compute_seed_hash(&self.rng_seed).to_bytes());
+        hash_concat.extend(self.roll_counts_hash.to_bytes());
+        hash_concat.extend(self.production_stats_hash.to_bytes());
+        self.cycle_global_hash = Hash::compute_from(&hash_concat);
+    }
+}
+
+/// Plain-data mirror of `CycleInfo`'s wire layout, used only to derive a
+/// serde `Serialize`/`Deserialize` view (for JSON/YAML export, debugging, or
+/// test fixtures) without exposing the derived hash fields (`roll_counts_hash`,
+/// `production_stats_hash`, `cycle_global_hash`), which are not part of the
+/// logical state.
+#[derive(Serialize, Deserialize)]
+struct CycleInfoSerdeView {
+    cycle: u64,
+    complete: bool,
+    roll_counts: BTreeMap<Address, u64>,
+    #[serde(
+        serialize_with = "serialize_rng_seed",
+        deserialize_with = "deserialize_rng_seed"
+    )]
+    rng_seed: BitVec<u8>,
+    production_stats: PreHashMap<Address, ProductionStats>,
+    final_state_hash_snapshot: Option<Hash>,
+}
+
+/// Serializes a `rng_seed` bitvec as a length-prefixed bit sequence: the
+/// number of bits followed by the bits packed into bytes
+fn serialize_rng_seed<S: serde::Serializer>(
+    seed: &BitVec<u8>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use serde::ser::SerializeTuple;
+    let mut tup = serializer.serialize_tuple(2)?;
+    tup.serialize_element(&(seed.len() as u64))?;
+    tup.serialize_element(&seed.clone().into_vec())?;
+    tup.end()
+}
+
+/// Deserializes a length-prefixed bit sequence back into a `rng_seed` bitvec
+fn deserialize_rng_seed<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BitVec<u8>, D::Error> {
+    let (bit_len, bytes): (u64, Vec<u8>) = Deserialize::deserialize(deserializer)?;
+    let mut seed = BitVec::<u8>::from_vec(bytes);
+    seed.truncate(bit_len as usize);
+    Ok(seed)
+}
+
+impl Serialize for CycleInfo {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        CycleInfoSerdeView {
+            cycle: self.cycle,
+            complete: self.complete,
+            roll_counts: self.roll_counts.clone(),
+            rng_seed: self.rng_seed.clone(),
+            production_stats: self.production_stats.clone(),
+            final_state_hash_snapshot: self.final_state_hash_snapshot,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CycleInfo {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let view = CycleInfoSerdeView::deserialize(deserializer)?;
+        // derived hashes are recomputed from the data rather than trusted from
+        // the input, matching `CycleInfoDeserializer`
+        let mut cycle = CycleInfo::new_with_hash(
+            view.cycle,
+            view.complete,
+            view.roll_counts,
+            view.rng_seed,
+            view.production_stats,
+        );
+        cycle.final_state_hash_snapshot = view.final_state_hash_snapshot;
+        Ok(cycle)
+    }
+}
+
+/// Serializes a `VecDeque<CycleInfo>` cycle history as a serde sequence of
+/// [`CycleInfo`] values, e.g. for JSON/YAML export or test fixtures
+pub fn serialize_cycle_history<S: serde::Serializer>(
+    value: &VecDeque<CycleInfo>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    value.serialize(serializer)
+}
+
+/// Deserializes a `VecDeque<CycleInfo>` cycle history from a serde sequence
+/// of [`CycleInfo`] values. Each cycle's derived hashes are recomputed rather
+/// than trusted from the input.
+pub fn deserialize_cycle_history<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<VecDeque<CycleInfo>, D::Error> {
+    VecDeque::<CycleInfo>::deserialize(deserializer)
 }
 
 #[derive(Clone)]
@@ -215,6 +645,96 @@ impl Serializer<CycleInfo> for CycleInfoSerializer {
     }
 }
 
+impl CycleInfoSerializer {
+    /// Exact number of bytes `serialize` would write for `value`, so callers
+    /// can `Vec::with_capacity(serialized_size)` before serializing, or
+    /// size a pre-allocated snapshot region.
+    pub fn serialized_size(&self, value: &CycleInfo) -> u64 {
+        let mut size = u64_varint_len(value.cycle) as u64; // cycle
+        size += 1; // complete
+        size += u64_varint_len(value.roll_counts.len() as u64) as u64;
+        let mut scratch = Vec::new();
+        for (addr, &count) in &value.roll_counts {
+            size += address_serialized_size(&self.address_ser, addr, &mut scratch)
+                + u64_varint_len(count) as u64;
+        }
+        size += bitvec_serialized_size(&value.rng_seed);
+        size += self.production_stats_ser.serialized_size(&value.production_stats);
+        size += 1 + value.final_state_hash_snapshot.map_or(0, |_| HASH_SIZE_BYTES as u64);
+        size
+    }
+
+    /// Pre-sized-buffer counterpart to `serialize`: writes `value` into the
+    /// front of `buf` and advances its cursor past the written bytes, without
+    /// growing any destination `Vec` (each field still goes through a small
+    /// scratch buffer). Panics if `buf` is smaller than
+    /// `self.serialized_size(value)`.
+    pub fn serialize_into(&self, value: &CycleInfo, buf: &mut &mut [u8]) {
+        let mut scratch = Vec::new();
+        write_field_into(&self.u64_ser, &value.cycle, &mut scratch, buf);
+        write_into_buf(buf, &[u8::from(value.complete)]);
+        write_field_into(
+            &self.u64_ser,
+            &(value.roll_counts.len() as u64),
+            &mut scratch,
+            buf,
+        );
+        for (addr, count) in &value.roll_counts {
+            write_field_into(&self.address_ser, addr, &mut scratch, buf);
+            write_field_into(&self.u64_ser, count, &mut scratch, buf);
+        }
+        write_field_into(&self.bitvec_ser, &value.rng_seed, &mut scratch, buf);
+        write_field_into(
+            &self.production_stats_ser,
+            &value.production_stats,
+            &mut scratch,
+            buf,
+        );
+        write_field_into(
+            &self.opt_hash_ser,
+            &value.final_state_hash_snapshot,
+            &mut scratch,
+            buf,
+        );
+    }
+
+    /// Streams `value` into `w`, one field at a time, instead of building up
+    /// an in-memory buffer first
+    pub fn serialize_to_writer<W: Write>(
+        &self,
+        value: &CycleInfo,
+        w: &mut W,
+    ) -> std::io::Result<()> {
+        let mut scratch = Vec::new();
+        write_field_to_writer(&self.u64_ser, &value.cycle, &mut scratch, w)?;
+        w.write_all(&[u8::from(value.complete)])?;
+        write_field_to_writer(
+            &self.u64_ser,
+            &(value.roll_counts.len() as u64),
+            &mut scratch,
+            w,
+        )?;
+        for (addr, count) in &value.roll_counts {
+            write_field_to_writer(&self.address_ser, addr, &mut scratch, w)?;
+            write_field_to_writer(&self.u64_ser, count, &mut scratch, w)?;
+        }
+        write_field_to_writer(&self.bitvec_ser, &value.rng_seed, &mut scratch, w)?;
+        write_field_to_writer(
+            &self.production_stats_ser,
+            &value.production_stats,
+            &mut scratch,
+            w,
+        )?;
+        write_field_to_writer(
+            &self.opt_hash_ser,
+            &value.final_state_hash_snapshot,
+            &mut scratch,
+            w,
+        )?;
+        Ok(())
+    }
+}
+
 #[derive(Clone)]
 #[allow(missing_docs)]
 /// Deserializer for `CycleInfo`
@@ -287,6 +807,98 @@ impl Deserializer<CycleInfo> for CycleInfoDeserializer {
     }
 }
 
+#[derive(Clone)]
+#[allow(missing_docs)]
+/// Serializer for `MerkleProof`
+pub struct MerkleProofSerializer {
+    pub u64_ser: U64VarIntSerializer,
+    pub hash_ser: HashSerializer,
+}
+
+impl Default for MerkleProofSerializer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MerkleProofSerializer {
+    /// Creates a new `MerkleProof` serializer
+    pub fn new() -> Self {
+        Self {
+            u64_ser: U64VarIntSerializer::new(),
+            hash_ser: HashSerializer::new(),
+        }
+    }
+}
+
+impl Serializer<MerkleProof> for MerkleProofSerializer {
+    fn serialize(&self, value: &MerkleProof, buffer: &mut Vec<u8>) -> Result<(), SerializeError> {
+        self.u64_ser
+            .serialize(&(value.steps.len() as u64), buffer)?;
+        for step in &value.steps {
+            buffer.push(u8::from(step.sibling_is_left));
+            self.hash_ser.serialize(&step.sibling, buffer)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+#[allow(missing_docs)]
+/// Deserializer for `MerkleProof`
+pub struct MerkleProofDeserializer {
+    length_deserializer: U64VarIntDeserializer,
+    pub hash_deserializer: HashDeserializer,
+}
+
+impl MerkleProofDeserializer {
+    /// Creates a new `MerkleProof` deserializer
+    pub fn new(max_proof_length: u64) -> MerkleProofDeserializer {
+        MerkleProofDeserializer {
+            length_deserializer: U64VarIntDeserializer::new(
+                Included(u64::MIN),
+                Included(max_proof_length),
+            ),
+            hash_deserializer: HashDeserializer::new(),
+        }
+    }
+}
+
+impl Deserializer<MerkleProof> for MerkleProofDeserializer {
+    fn deserialize<'a, E: ParseError<&'a [u8]> + ContextError<&'a [u8]>>(
+        &self,
+        buffer: &'a [u8],
+    ) -> IResult<&'a [u8], MerkleProof, E> {
+        context(
+            "Failed MerkleProof deserialization",
+            length_count(
+                context("Failed length deserialization", |input| {
+                    self.length_deserializer.deserialize(input)
+                }),
+                tuple((
+                    context(
+                        "Failed sibling_is_left deserialization",
+                        alt((value(true, tag(&[1])), value(false, tag(&[0])))),
+                    ),
+                    context("Failed sibling deserialization", |input| {
+                        self.hash_deserializer.deserialize(input)
+                    }),
+                )),
+            ),
+        )
+        .map(|steps| MerkleProof {
+            steps: steps
+                .into_iter()
+                .map(|(sibling_is_left, sibling)| MerkleProofStep {
+                    sibling,
+                    sibling_is_left,
+                })
+                .collect(),
+        })
+        .parse(buffer)
+    }
+}
+
 /// Block production statistics
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct ProductionStats {
@@ -364,6 +976,67 @@ impl Serializer<PreHashMap<Address, ProductionStats>> for ProductionStatsSeriali
     }
 }
 
+impl ProductionStatsSerializer {
+    /// Exact number of bytes `serialize` would write for `value`
+    pub fn serialized_size(&self, value: &PreHashMap<Address, ProductionStats>) -> u64 {
+        let mut size = u64_varint_len(value.len() as u64) as u64;
+        let mut scratch = Vec::new();
+        for (addr, prod_stats) in value.iter() {
+            size += address_serialized_size(&self.address_ser, addr, &mut scratch)
+                + u64_varint_len(prod_stats.block_success_count) as u64
+                + u64_varint_len(prod_stats.block_failure_count) as u64;
+        }
+        size
+    }
+
+    /// Pre-sized-buffer counterpart to `serialize`: writes `value` into the
+    /// front of `buf` and advances its cursor. Panics if `buf` is too small.
+    pub fn serialize_into(
+        &self,
+        value: &PreHashMap<Address, ProductionStats>,
+        buf: &mut &mut [u8],
+    ) {
+        let mut scratch = Vec::new();
+        write_field_into(&self.u64_ser, &(value.len() as u64), &mut scratch, buf);
+        for (
+            addr,
+            ProductionStats {
+                block_success_count,
+                block_failure_count,
+            },
+        ) in value.iter()
+        {
+            write_field_into(&self.address_ser, addr, &mut scratch, buf);
+            write_field_into(&self.u64_ser, block_success_count, &mut scratch, buf);
+            write_field_into(&self.u64_ser, block_failure_count, &mut scratch, buf);
+        }
+    }
+
+    /// Streams `value` into `w`, one entry at a time, instead of building up
+    /// an in-memory buffer first
+    pub fn serialize_to_writer<W: Write>(
+        &self,
+        value: &PreHashMap<Address, ProductionStats>,
+        w: &mut W,
+    ) -> std::io::Result<()> {
+        let mut scratch = Vec::new();
+        write_field_to_writer(&self.u64_ser, &(value.len() as u64), &mut scratch, w)?;
+        for (
+            addr,
+            ProductionStats {
+                block_success_count,
+                block_failure_count,
+            },
+        ) in value.iter()
+        {
+            write_field_to_writer(&self.address_ser, addr, &mut scratch, w)?;
+            write_field_to_writer(&self.u64_ser, block_success_count, &mut scratch, w)?;
+            write_field_to_writer(&self.u64_ser, block_failure_count, &mut scratch, w)?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone)]
 #[allow(missing_docs)]
 /// Deserializer for `ProductionStats`
@@ -516,6 +1189,57 @@ impl Serializer<VecDeque<CycleInfo>> for CycleHistorySerializer {
     }
 }
 
+impl CycleHistorySerializer {
+    /// Exact number of bytes `serialize` would write for `value`. Callers can
+    /// `Vec::with_capacity(serializer.serialized_size(&cycle_history) as usize)`
+    /// before serializing, or serialize straight into a pre-sized snapshot region.
+    pub fn serialized_size(&self, value: &VecDeque<CycleInfo>) -> u64 {
+        let mut size = u64_varint_len(value.len() as u64) as u64;
+        for cycle_info in value.iter() {
+            size += self.cycle_info_serializer.serialized_size(cycle_info);
+        }
+        size
+    }
+
+    /// Pre-sized-buffer counterpart to `serialize`: writes `value` into the
+    /// front of `buf` and advances its cursor. Panics if `buf` is smaller than
+    /// `self.serialized_size(value)`.
+    pub fn serialize_into(&self, value: &VecDeque<CycleInfo>, buf: &mut &mut [u8]) {
+        let mut scratch = Vec::new();
+        write_field_into(
+            &self.u64_serializer,
+            &(value.len() as u64),
+            &mut scratch,
+            buf,
+        );
+        for cycle_info in value.iter() {
+            self.cycle_info_serializer.serialize_into(cycle_info, buf);
+        }
+    }
+
+    /// Streams a cycle-history snapshot into `w`, one `CycleInfo` at a time,
+    /// so a node can dump it straight into a file or socket with bounded
+    /// memory instead of materializing the whole encoded blob first. Writes
+    /// the exact same byte layout as `serialize`.
+    pub fn serialize_to_writer<W: Write>(
+        &self,
+        value: &VecDeque<CycleInfo>,
+        w: &mut W,
+    ) -> std::io::Result<()> {
+        let mut scratch = Vec::new();
+        write_field_to_writer(
+            &self.u64_serializer,
+            &(value.len() as u64),
+            &mut scratch,
+            w,
+        )?;
+        for cycle_info in value.iter() {
+            self.cycle_info_serializer.serialize_to_writer(cycle_info, w)?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone)]
 #[allow(missing_docs)]
 /// Deserializer for cycle history, useful when restarting from a snapshot
@@ -563,3 +1287,420 @@ impl Deserializer<Vec<CycleInfo>> for CycleHistoryDeserializer {
         .parse(buffer)
     }
 }
+
+impl CycleHistoryDeserializer {
+    /// Reads a cycle-history snapshot back from `r`, the `io::Read`
+    /// counterpart to `CycleHistorySerializer::serialize_to_writer`: reads
+    /// the length prefix then pulls one `CycleInfo` at a time, so the encoded
+    /// bytes buffered at any moment are bounded by a single cycle (plus
+    /// read-ahead) rather than the whole snapshot. The decoded cycles are all
+    /// collected into the returned `Vec`.
+    pub fn deserialize_from_reader<R: Read>(&self, r: &mut R) -> std::io::Result<Vec<CycleInfo>> {
+        let len = read_u64_varint_from_reader(&self.u64_deserializer, r)?;
+        let mut result = Vec::with_capacity(len as usize);
+        // shared across iterations: a read can over-shoot into the next
+        // CycleInfo's bytes, which must carry over instead of being dropped
+        let mut residual = Vec::new();
+        for _ in 0..len {
+            result.push(deserialize_one_from_reader(
+                &self.cycle_info_deserializer,
+                r,
+                &mut residual,
+            )?);
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use massa_signature::KeyPair;
+
+    fn random_address() -> Address {
+        Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key())
+    }
+
+    #[test]
+    fn cycle_history_writer_reader_round_trip_multi_cycle() {
+        let mut roll_counts_a = BTreeMap::new();
+        roll_counts_a.insert(random_address(), 10);
+        let mut roll_counts_b = BTreeMap::new();
+        roll_counts_b.insert(random_address(), 20);
+        roll_counts_b.insert(random_address(), 30);
+
+        let mut history = VecDeque::new();
+        history.push_back(CycleInfo::new_with_hash(
+            0,
+            false,
+            roll_counts_a,
+            BitVec::new(),
+            PreHashMap::default(),
+        ));
+        history.push_back(CycleInfo::new_with_hash(
+            1,
+            true,
+            roll_counts_b,
+            BitVec::new(),
+            PreHashMap::default(),
+        ));
+
+        let serializer = CycleHistorySerializer::new();
+        let mut buffer = Vec::new();
+        serializer
+            .serialize_to_writer(&history, &mut buffer)
+            .unwrap();
+
+        let deserializer = CycleHistoryDeserializer::new(100, 100, 100);
+        let mut reader = std::io::Cursor::new(buffer);
+        let read_back = deserializer.deserialize_from_reader(&mut reader).unwrap();
+
+        assert_eq!(read_back, Vec::from(history));
+    }
+
+    /// Builds a `CycleInfo` exercising every variable-length field (two roll
+    /// entries, one production-stats entry, a non-empty `rng_seed`, and a
+    /// populated `final_state_hash_snapshot`), so `serialized_size` is
+    /// checked against more than the empty/default case.
+    fn representative_cycle_info() -> CycleInfo {
+        let mut roll_counts = BTreeMap::new();
+        roll_counts.insert(random_address(), 10);
+        roll_counts.insert(random_address(), u64::MAX);
+
+        let mut production_stats = PreHashMap::default();
+        production_stats.insert(
+            random_address(),
+            ProductionStats {
+                block_success_count: 5,
+                block_failure_count: 2,
+            },
+        );
+
+        // deliberately not a multiple of 8 bits, to exercise the trailing-bit
+        // handling in both the nom bitvec codec and the serde one
+        let mut rng_seed = BitVec::<u8>::new();
+        for bit in [
+            true, false, true, true, false, true, false, false, true, true, true, false, true,
+        ] {
+            rng_seed.push(bit);
+        }
+
+        let mut cycle =
+            CycleInfo::new_with_hash(7, true, roll_counts, rng_seed, production_stats);
+        cycle.final_state_hash_snapshot = Some(Hash::compute_from(b"test"));
+        cycle
+    }
+
+    #[test]
+    fn cycle_info_serde_json_round_trip() {
+        let cycle = representative_cycle_info();
+
+        let json = serde_json::to_string(&cycle).unwrap();
+        let deserialized: CycleInfo = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(deserialized, cycle);
+        // the derived hashes must be recomputed from the data, not trusted
+        // from the input, and must match the original's
+        assert_eq!(deserialized.roll_counts_hash, cycle.roll_counts_hash);
+        assert_eq!(
+            deserialized.production_stats_hash,
+            cycle.production_stats_hash
+        );
+        assert_eq!(deserialized.cycle_global_hash, cycle.cycle_global_hash);
+    }
+
+    #[test]
+    fn cycle_history_serde_json_round_trip() {
+        let mut history = VecDeque::new();
+        history.push_back(representative_cycle_info());
+        history.push_back(representative_cycle_info());
+
+        let mut buffer = Vec::new();
+        let mut json_serializer = serde_json::Serializer::new(&mut buffer);
+        serialize_cycle_history(&history, &mut json_serializer).unwrap();
+
+        let mut json_deserializer =
+            serde_json::Deserializer::from_slice(&buffer);
+        let deserialized = deserialize_cycle_history(&mut json_deserializer).unwrap();
+
+        assert_eq!(deserialized, history);
+    }
+
+    #[test]
+    fn cycle_info_serialized_size_matches_actual_serialize_len() {
+        let cycle = representative_cycle_info();
+        let serializer = CycleInfoSerializer::new();
+
+        let mut buffer = Vec::new();
+        serializer.serialize(&cycle, &mut buffer).unwrap();
+
+        assert_eq!(serializer.serialized_size(&cycle), buffer.len() as u64);
+    }
+
+    #[test]
+    fn cycle_info_serialize_into_matches_serialize() {
+        let cycle = representative_cycle_info();
+        let serializer = CycleInfoSerializer::new();
+
+        let mut expected = Vec::new();
+        serializer.serialize(&cycle, &mut expected).unwrap();
+
+        let mut actual = vec![0u8; serializer.serialized_size(&cycle) as usize];
+        {
+            let mut cursor: &mut [u8] = &mut actual;
+            serializer.serialize_into(&cycle, &mut cursor);
+            assert!(cursor.is_empty(), "serialize_into must fill the buffer exactly");
+        }
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn cycle_history_serialized_size_matches_actual_serialize_len() {
+        let mut history = VecDeque::new();
+        history.push_back(representative_cycle_info());
+        history.push_back(representative_cycle_info());
+
+        let serializer = CycleHistorySerializer::new();
+        let mut buffer = Vec::new();
+        serializer.serialize(&history, &mut buffer).unwrap();
+
+        assert_eq!(serializer.serialized_size(&history), buffer.len() as u64);
+
+        let mut actual = vec![0u8; serializer.serialized_size(&history) as usize];
+        {
+            let mut cursor: &mut [u8] = &mut actual;
+            serializer.serialize_into(&history, &mut cursor);
+            assert!(cursor.is_empty(), "serialize_into must fill the buffer exactly");
+        }
+        assert_eq!(actual, buffer);
+    }
+
+    #[test]
+    fn roll_merkle_root_of_empty_map_is_initial_hash() {
+        let root = build_roll_merkle_root(&BTreeMap::new());
+        assert_eq!(root, Hash::from_bytes(CYCLE_INFO_HASH_INITIAL_BYTES));
+    }
+
+    #[test]
+    fn roll_inclusion_proof_is_none_for_unknown_address() {
+        let mut roll_counts = BTreeMap::new();
+        roll_counts.insert(random_address(), 1);
+        let cycle = CycleInfo::new_with_hash(
+            0,
+            false,
+            roll_counts,
+            BitVec::new(),
+            PreHashMap::default(),
+        );
+        assert!(cycle.roll_inclusion_proof(&random_address()).is_none());
+    }
+
+    /// Every leaf of a roll-counts tree of size `leaf_count` (1, 2, 3, 4...
+    /// exercises both even levels and the odd-node-duplication path) proves
+    /// and verifies against the tree's root, and a tampered count is rejected.
+    #[test]
+    fn roll_inclusion_proof_round_trips_and_verifies_for_various_sizes() {
+        for leaf_count in [1usize, 2, 3, 4, 5, 7] {
+            let mut roll_counts = BTreeMap::new();
+            let mut addrs = Vec::new();
+            for i in 0..leaf_count {
+                let addr = random_address();
+                roll_counts.insert(addr, (i as u64) + 1);
+                addrs.push(addr);
+            }
+            let cycle = CycleInfo::new_with_hash(
+                0,
+                false,
+                roll_counts.clone(),
+                BitVec::new(),
+                PreHashMap::default(),
+            );
+            let root = build_roll_merkle_root(&roll_counts);
+
+            for (i, addr) in addrs.iter().enumerate() {
+                let count = (i as u64) + 1;
+                let proof = cycle.roll_inclusion_proof(addr).unwrap();
+                assert!(
+                    verify_roll_proof(&root, addr, count, &proof),
+                    "leaf_count={leaf_count} index={i} should verify"
+                );
+                assert!(
+                    !verify_roll_proof(&root, addr, count + 1, &proof),
+                    "leaf_count={leaf_count} index={i} tampered count should not verify"
+                );
+
+                // the proof round-trips through its wire serialization and
+                // still verifies afterwards
+                let proof_serializer = MerkleProofSerializer::new();
+                let mut buffer = Vec::new();
+                proof_serializer.serialize(&proof, &mut buffer).unwrap();
+                let proof_deserializer = MerkleProofDeserializer::new(64);
+                let (rest, deserialized_proof) = proof_deserializer
+                    .deserialize::<nom::error::Error<&[u8]>>(&buffer)
+                    .unwrap();
+                assert!(rest.is_empty());
+                assert_eq!(deserialized_proof, proof);
+                assert!(verify_roll_proof(&root, addr, count, &deserialized_proof));
+            }
+
+            // tampering with the root itself must also be rejected
+            let wrong_root = Hash::compute_from(b"not the real root");
+            let proof = cycle.roll_inclusion_proof(&addrs[0]).unwrap();
+            assert!(!verify_roll_proof(&wrong_root, &addrs[0], 1, &proof));
+        }
+    }
+
+    /// Production-stats twin of the roll-counts Merkle proof test above
+    #[test]
+    fn production_stat_inclusion_proof_round_trips_and_verifies_for_various_sizes() {
+        for leaf_count in [1usize, 2, 3, 4, 5, 7] {
+            let mut production_stats = PreHashMap::default();
+            let mut entries = Vec::new();
+            for i in 0..leaf_count {
+                let addr = random_address();
+                let stats = ProductionStats {
+                    block_success_count: i as u64,
+                    block_failure_count: (i as u64) + 1,
+                };
+                production_stats.insert(addr, stats);
+                entries.push((addr, stats));
+            }
+            let cycle = CycleInfo::new_with_hash(
+                0,
+                false,
+                BTreeMap::new(),
+                BitVec::new(),
+                production_stats.clone(),
+            );
+            let root = build_production_stats_merkle_root(&production_stats);
+
+            for (i, (addr, stats)) in entries.iter().enumerate() {
+                let proof = cycle.production_stat_inclusion_proof(addr).unwrap();
+                assert!(
+                    verify_production_stat_proof(&root, addr, stats, &proof),
+                    "leaf_count={leaf_count} index={i} should verify"
+                );
+                let tampered = ProductionStats {
+                    block_success_count: stats.block_success_count + 1,
+                    ..*stats
+                };
+                assert!(!verify_production_stat_proof(&root, addr, &tampered, &proof));
+
+                let proof_serializer = MerkleProofSerializer::new();
+                let mut buffer = Vec::new();
+                proof_serializer.serialize(&proof, &mut buffer).unwrap();
+                let proof_deserializer = MerkleProofDeserializer::new(64);
+                let (rest, deserialized_proof) = proof_deserializer
+                    .deserialize::<nom::error::Error<&[u8]>>(&buffer)
+                    .unwrap();
+                assert!(rest.is_empty());
+                assert_eq!(deserialized_proof, proof);
+                assert!(verify_production_stat_proof(
+                    &root,
+                    addr,
+                    stats,
+                    &deserialized_proof
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn set_roll_count_matches_full_recompute() {
+        let addr_a = random_address();
+        let addr_b = random_address();
+        let mut roll_counts = BTreeMap::new();
+        roll_counts.insert(addr_a, 10);
+
+        let mut cycle = CycleInfo::new_with_hash(
+            0,
+            false,
+            roll_counts.clone(),
+            BitVec::new(),
+            PreHashMap::default(),
+        );
+
+        // update an existing entry, then add a new one, incrementally
+        cycle.set_roll_count(addr_a, 42);
+        cycle.set_roll_count(addr_b, 7);
+
+        roll_counts.insert(addr_a, 42);
+        roll_counts.insert(addr_b, 7);
+        let expected = CycleInfo::new_with_hash(
+            0,
+            false,
+            roll_counts,
+            BitVec::new(),
+            PreHashMap::default(),
+        );
+
+        assert_eq!(cycle.roll_counts_hash, expected.roll_counts_hash);
+        assert_eq!(cycle.cycle_global_hash, expected.cycle_global_hash);
+        assert_eq!(cycle.roll_counts, expected.roll_counts);
+    }
+
+    #[test]
+    fn apply_production_stat_matches_full_recompute() {
+        let addr_a = random_address();
+        let addr_b = random_address();
+        let mut production_stats = PreHashMap::default();
+        production_stats.insert(
+            addr_a,
+            ProductionStats {
+                block_success_count: 3,
+                block_failure_count: 1,
+            },
+        );
+
+        let mut cycle = CycleInfo::new_with_hash(
+            0,
+            false,
+            BTreeMap::new(),
+            BitVec::new(),
+            production_stats.clone(),
+        );
+
+        // extend an existing entry, then add a new one, incrementally
+        cycle.apply_production_stat(
+            addr_a,
+            &ProductionStats {
+                block_success_count: 2,
+                block_failure_count: 0,
+            },
+        );
+        cycle.apply_production_stat(
+            addr_b,
+            &ProductionStats {
+                block_success_count: 1,
+                block_failure_count: 1,
+            },
+        );
+
+        production_stats.insert(
+            addr_a,
+            ProductionStats {
+                block_success_count: 5,
+                block_failure_count: 1,
+            },
+        );
+        production_stats.insert(
+            addr_b,
+            ProductionStats {
+                block_success_count: 1,
+                block_failure_count: 1,
+            },
+        );
+        let expected = CycleInfo::new_with_hash(
+            0,
+            false,
+            BTreeMap::new(),
+            BitVec::new(),
+            production_stats,
+        );
+
+        assert_eq!(cycle.production_stats_hash, expected.production_stats_hash);
+        assert_eq!(cycle.cycle_global_hash, expected.cycle_global_hash);
+        assert_eq!(cycle.production_stats, expected.production_stats);
+    }
+}